@@ -3,8 +3,9 @@
 //! This module implements a dynamic dereference table that maps “tiny pointers”
 //! (compact indices augmented with generation counters) to values of type `T`.
 //!
-//! The table uses a free list to manage available slots and doubles its capacity
-//! when full, while preserving the validity of currently allocated indices.
+//! The table uses an intrusive free list threaded through the free slots
+//! themselves and grows by allocating additional pages when full, while
+//! preserving the validity of currently allocated indices.
 //!
 //! With generational pointers, each slot stores a generation counter. When a slot
 //! is freed, its generation is incremented. Any pointer holding an old generation
@@ -34,36 +35,151 @@
 //!     table.allocate(i);
 //! }
 //! assert!(table.capacity() >= 100);
-//! ``` 
+//! ```
 
+use std::collections::TryReserveError;
 use std::fmt;
 
+/// A type usable as the compact index component of a [`TinyPointer`].
+///
+/// Implemented for `u8`, `u16`, and `u32`. Pick the narrowest one that can
+/// hold the table's maximum capacity to shrink each handle below the
+/// default 8 bytes.
+pub trait PtrInx: Copy + Eq + fmt::Debug {
+    /// The number of bits `Self` occupies in a [`TinyPointer::to_bits`] encoding.
+    const BITS: u32;
+    /// The maximum representable value. Reserved to mean "no pointer" and
+    /// never handed out by `allocate`.
+    const MAX: Self;
+
+    fn from_usize(value: usize) -> Self;
+    fn to_usize(self) -> usize;
+    fn to_u64(self) -> u64;
+    fn from_u64(value: u64) -> Self;
+}
+
+/// A type usable as the generation component of a [`TinyPointer`].
+///
+/// Implemented for `u8`, `u16`, and `u32`. Pick a width wide enough that a
+/// single slot is never reused more times than the type can count.
+pub trait PtrGen: Copy + Eq + fmt::Debug {
+    /// The maximum representable generation.
+    const MAX: Self;
+    /// The generation a freshly allocated page's slots start at.
+    const ZERO: Self;
+
+    fn wrapping_add_one(self) -> Self;
+    fn to_u64(self) -> u64;
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_ptr_inx {
+    ($($t:ty),* $(,)?) => {$(
+        impl PtrInx for $t {
+            const BITS: u32 = <$t>::BITS;
+            const MAX: Self = <$t>::MAX;
+
+            #[inline]
+            fn from_usize(value: usize) -> Self {
+                value as $t
+            }
+
+            #[inline]
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+
+            #[inline]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            #[inline]
+            fn from_u64(value: u64) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+impl_ptr_inx!(u8, u16, u32);
+
+macro_rules! impl_ptr_gen {
+    ($($t:ty),* $(,)?) => {$(
+        impl PtrGen for $t {
+            const MAX: Self = <$t>::MAX;
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn wrapping_add_one(self) -> Self {
+                self.wrapping_add(1)
+            }
+
+            #[inline]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            #[inline]
+            fn from_u64(value: u64) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+impl_ptr_gen!(u8, u16, u32);
+
 /// A generational tiny pointer.
 ///
-/// This pointer consists of an index (a compact `u32`) and a generation counter.
-/// When a slot is freed and later reused, its generation is incremented,
-/// which invalidates any old pointer to that slot.
+/// This pointer consists of a compact index (`I`, `u32` by default) and a
+/// generation counter (`G`, `u32` by default). When a slot is freed and
+/// later reused, its generation is incremented, which invalidates any old
+/// pointer to that slot. Narrower `I`/`G` choices shrink the handle below
+/// the default 8 bytes; see [`DynamicTinyPointerTable::with_widths`].
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct TinyPointer {
-    index: u32,
-    generation: u32,
+pub struct TinyPointer<I = u32, G = u32> {
+    index: I,
+    generation: G,
 }
 
-impl TinyPointer {
+impl<I: PtrInx, G: PtrGen> TinyPointer<I, G> {
     /// Returns the index associated with this pointer.
     #[inline]
     pub fn index(&self) -> usize {
-        self.index as usize
+        self.index.to_usize()
     }
-    
+
     /// Returns the generation stored in this pointer.
     #[inline]
-    pub fn generation(&self) -> u32 {
+    pub fn generation(&self) -> G {
         self.generation
     }
+
+    /// Packs this pointer into a single `u64`, generation in the high bits
+    /// and index in the low `I::BITS` bits, for storage in FFI structs or
+    /// serialized handle tables.
+    #[inline]
+    pub fn to_bits(self) -> u64 {
+        (self.generation.to_u64() << I::BITS) | self.index.to_u64()
+    }
+
+    /// Unpacks a `TinyPointer` previously produced by [`to_bits`](Self::to_bits).
+    ///
+    /// `I::MAX` is reserved to mean "no pointer", so `from_bits` returns
+    /// `None` for any encoding with that index, letting callers use
+    /// `I::MAX` as an explicit null sentinel.
+    #[inline]
+    pub fn from_bits(bits: u64) -> Option<Self> {
+        let index_mask = (1u64 << I::BITS) - 1;
+        let index_bits = bits & index_mask;
+        if index_bits == I::MAX.to_u64() {
+            return None;
+        }
+        let generation_bits = bits >> I::BITS;
+        Some(Self { index: I::from_u64(index_bits), generation: G::from_u64(generation_bits) })
+    }
 }
 
-impl fmt::Display for TinyPointer {
+impl<I: PtrInx, G: PtrGen + fmt::Display> fmt::Display for TinyPointer<I, G> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "TinyPointer({}, gen: {})", self.index(), self.generation)
     }
@@ -71,52 +187,229 @@ impl fmt::Display for TinyPointer {
 
 /// A slot in the dynamic table.
 ///
-/// Each slot stores an optional value along with a generation counter.
-/// The generation counter is used to validate that a `TinyPointer`
-/// is not stale.
-struct Slot<T> {
-    value: Option<Box<T>>,
-    generation: u32,
+/// An occupied slot stores its value alongside the generation counter that
+/// validates `TinyPointer`s into it. A free slot instead stores the index of
+/// the next free slot, threading the free list intrusively through the
+/// slots themselves rather than through a separate side vector. A retired
+/// slot is one that was freed while its generation counter was at `G::MAX`;
+/// it holds nothing and is not on the free list. See the note on
+/// [`DynamicTinyPointerTable::free`].
+enum Entry<T, I, G> {
+    Occupied { value: T, generation: G },
+    Free { next_free: Option<I>, generation: G },
+    Retired,
+}
+
+/// A page of slots, boxed so that appending a new page never moves the ones
+/// already allocated.
+type FreePage<T, I, G> = Box<[Entry<T, I, G>]>;
+
+/// The error returned by [`DynamicTinyPointerTable::try_reserve`] when
+/// the table cannot be grown by another page.
+#[derive(Debug)]
+pub enum ReserveError {
+    /// The underlying allocator reported failure.
+    Alloc(TryReserveError),
+    /// Growing by another page would exceed the maximum index representable
+    /// by `I`. Unlike `Alloc`, this is a static property of `I` and the
+    /// table's current capacity, not a transient allocator condition — the
+    /// table cannot grow further no matter how many times the caller retries.
+    IndexSpaceExhausted,
+}
+
+impl fmt::Display for ReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReserveError::Alloc(source) => write!(f, "{source}"),
+            ReserveError::IndexSpaceExhausted => {
+                write!(f, "growing would exceed the maximum index representable by I")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReserveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReserveError::Alloc(source) => Some(source),
+            ReserveError::IndexSpaceExhausted => None,
+        }
+    }
+}
+
+impl From<TryReserveError> for ReserveError {
+    fn from(source: TryReserveError) -> Self {
+        ReserveError::Alloc(source)
+    }
+}
+
+/// The error returned by [`DynamicTinyPointerTable::try_allocate`] when
+/// growing the table fails.
+///
+/// The value that could not be stored is handed back so the caller does
+/// not lose it.
+pub struct AllocError<T> {
+    pub value: T,
+    pub source: ReserveError,
+}
+
+impl<T> fmt::Debug for AllocError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllocError").field("source", &self.source).finish()
+    }
+}
+
+impl<T> fmt::Display for AllocError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to allocate a tiny pointer slot: {}", self.source)
+    }
+}
+
+impl<T> std::error::Error for AllocError<T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
 }
 
 /// A dynamic dereference table using generational tiny pointers.
 ///
-/// This table stores values of type `T` in a vector. It uses a free list
-/// to keep track of available slots and doubles its capacity when needed.
-/// Returned pointers are valid only if their generation matches the current
-/// generation of the slot.
-pub struct DynamicTinyPointerTable<T: Clone> {
-    slots: Vec<Slot<T>>,
-    free_list: Vec<usize>,
+/// Slots are stored in *pages*: page `k` holds `2^(k + base)` slots, where
+/// `base` is derived from the table's initial capacity. Pages are appended
+/// (never reallocated or moved) as the table grows, so a reference into an
+/// existing page remains valid for the lifetime of the table even while new
+/// pages are added. This also bounds the size of any single allocation,
+/// unlike a flat `Vec` that must copy its entire contents on every growth.
+///
+/// Values are stored inline (no per-element boxing), so `T` needs no trait
+/// bounds beyond `Sized` — non-`Clone` payloads such as `File` or `Mutex`
+/// work just as well as plain data. Returned pointers are valid only if
+/// their generation matches the current generation of the slot.
+///
+/// Generations never wrap: a slot whose generation counter reaches `G::MAX`
+/// is permanently retired instead of being recycled, so a table can lose at
+/// most one slot per `2^G` reuses of a single index rather than risk a
+/// wrapped generation resurrecting a stale pointer. See
+/// [`free`](Self::free) and [`retired`](Self::retired).
+///
+/// `I` and `G` are the index and generation widths (`u32` by default); see
+/// [`with_widths`](Self::with_widths) to pick narrower ones.
+pub struct DynamicTinyPointerTable<T, I = u32, G = u32> {
+    pages: Vec<FreePage<T, I, G>>,
+    /// `log2` of the first page's length. Page `k` has length `2^(k + base)`.
+    base: u32,
+    /// Sum of the lengths of all pages allocated so far.
+    capacity: usize,
+    /// Number of currently occupied slots.
+    allocated: usize,
+    /// Index of the head of the free list, or `None` if there is no free slot.
+    first_free: Option<I>,
+    /// Number of slots permanently retired due to generation exhaustion.
+    retired: usize,
 }
 
-impl<T: Clone> DynamicTinyPointerTable<T> {
-    /// Creates a new table with the specified initial capacity.
+impl<T> DynamicTinyPointerTable<T> {
+    /// Creates a new table with the specified initial capacity, using the
+    /// default `u32` index and generation widths.
+    ///
+    /// The first page is sized to the next power of two greater than or
+    /// equal to `initial_capacity`.
     ///
     /// # Panics
     ///
     /// Panics if `initial_capacity` is 0.
     pub fn new(initial_capacity: usize) -> Self {
+        Self::with_widths(initial_capacity)
+    }
+}
+
+impl<T, I: PtrInx, G: PtrGen> DynamicTinyPointerTable<T, I, G> {
+    /// Creates a new table with the specified initial capacity, using
+    /// whichever index (`I`) and generation (`G`) widths this table was
+    /// instantiated with. Use a turbofish to pick widths narrower than the
+    /// `u32`/`u32` default, e.g. `DynamicTinyPointerTable::<T, u16, u8>::with_widths(n)`.
+    ///
+    /// The first page is sized to the next power of two greater than or
+    /// equal to `initial_capacity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `initial_capacity` is 0, or if it, rounded up to the next
+    /// power of two, exceeds the maximum index representable by `I`.
+    pub fn with_widths(initial_capacity: usize) -> Self {
         assert!(initial_capacity > 0, "initial_capacity must be > 0");
-        let mut slots = Vec::with_capacity(initial_capacity);
-        let mut free_list = Vec::with_capacity(initial_capacity);
-        for i in 0..initial_capacity {
-            slots.push(Slot { value: None, generation: 0 });
-            free_list.push(i);
+        let first_page_len = initial_capacity.next_power_of_two();
+        assert!(
+            first_page_len <= I::MAX.to_usize(),
+            "DynamicTinyPointerTable: initial_capacity exceeds the maximum index representable by I"
+        );
+        let base = first_page_len.trailing_zeros();
+        let first_page = Self::try_new_free_page(0, first_page_len, None)
+            .expect("failed to allocate the initial page");
+        Self {
+            pages: vec![first_page],
+            base,
+            capacity: first_page_len,
+            allocated: 0,
+            first_free: Some(I::from_usize(0)),
+            retired: 0,
         }
-        Self { slots, free_list }
+    }
+
+    /// Allocates a fresh page of `len` free slots starting at global index
+    /// `start_index`, threading a free-list chain through them whose tail
+    /// points at `tail_next` (the previous head of the free list, if any).
+    ///
+    /// Uses `Vec::try_reserve_exact` so callers can surface an allocation
+    /// failure as an error instead of aborting.
+    fn try_new_free_page(
+        start_index: usize,
+        len: usize,
+        tail_next: Option<I>,
+    ) -> Result<FreePage<T, I, G>, TryReserveError> {
+        let mut page = Vec::new();
+        page.try_reserve_exact(len)?;
+        for i in 0..len {
+            let next_free = if i + 1 < len {
+                Some(I::from_usize(start_index + i + 1))
+            } else {
+                tail_next
+            };
+            page.push(Entry::Free { next_free, generation: G::ZERO });
+        }
+        Ok(page.into_boxed_slice())
+    }
+
+    /// Returns the global index of the first slot in page `page_idx`.
+    fn page_start(page_idx: usize, base: u32) -> usize {
+        (1usize << (page_idx as u32 + base)) - (1usize << base)
+    }
+
+    /// Decodes a global slot index into `(page, offset)`.
+    fn locate(&self, index: usize) -> (usize, usize) {
+        let first_page_len = 1usize << self.base;
+        let virtual_index = index + first_page_len;
+        let page = (virtual_index.ilog2() - self.base) as usize;
+        (page, index - Self::page_start(page, self.base))
     }
 
     /// Returns the current total capacity of the table.
     #[inline]
     pub fn capacity(&self) -> usize {
-        self.slots.len()
+        self.capacity
     }
 
     /// Returns the number of allocated (non-free) entries.
     #[inline]
     pub fn allocated(&self) -> usize {
-        self.slots.len() - self.free_list.len()
+        self.allocated
+    }
+
+    /// Returns the number of slots permanently retired due to generation
+    /// exhaustion. Retired slots still count toward `capacity()` but can
+    /// never be allocated again; see the note on [`free`](Self::free).
+    #[inline]
+    pub fn retired(&self) -> usize {
+        self.retired
     }
 
     /// Returns the current load factor (allocated slots divided by capacity).
@@ -125,77 +418,305 @@ impl<T: Clone> DynamicTinyPointerTable<T> {
         self.allocated() as f64 / self.capacity() as f64
     }
 
-    /// Allocates a slot for `value`, resizing the table if necessary,
+    /// Allocates a slot for `value`, adding a new page if necessary,
     /// and returns a `TinyPointer` (including the current generation).
     ///
-    /// This operation is amortized constant-time.
-    pub fn allocate(&mut self, value: T) -> TinyPointer {
-        if self.free_list.is_empty() {
+    /// This operation is amortized constant-time. A call that triggers
+    /// growth is `O(current capacity)`, since the new page doubles the
+    /// table's size and every slot in it is initialized — but unlike a flat
+    /// `Vec`, that cost never includes copying existing slots: pages are
+    /// appended, not reallocated, so already-allocated slots are untouched
+    /// and references into them stay valid across the growth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if growing the table fails to allocate, or would exceed the
+    /// maximum index representable by `I`. Use [`try_allocate`](Self::try_allocate)
+    /// in contexts that cannot tolerate an OOM abort.
+    pub fn allocate(&mut self, value: T) -> TinyPointer<I, G> {
+        if self.first_free.is_none() {
             self.resize();
         }
-        // Pop a free index.
-        let idx = self.free_list.pop().expect("free_list should not be empty");
-        let slot = &mut self.slots[idx];
-        slot.value = Some(Box::new(value));
+        self.allocate_into_free_slot(value)
+    }
+
+    /// Allocates a slot for `value`, growing the table with a fallible
+    /// reservation instead of panicking on OOM or on index-space exhaustion.
+    ///
+    /// On failure, `value` is returned inside the `AllocError` so the
+    /// caller does not lose it.
+    pub fn try_allocate(&mut self, value: T) -> Result<TinyPointer<I, G>, AllocError<T>> {
+        if self.first_free.is_none() {
+            if let Err(source) = self.try_reserve() {
+                return Err(AllocError { value, source });
+            }
+        }
+        Ok(self.allocate_into_free_slot(value))
+    }
+
+    /// Pops the head of the free list (which must be `Some`) and occupies it
+    /// with `value`.
+    fn allocate_into_free_slot(&mut self, value: T) -> TinyPointer<I, G> {
+        let idx = self.first_free.expect("first_free should not be None after reserving").to_usize();
+        let (page, offset) = self.locate(idx);
+        let slot = &mut self.pages[page][offset];
+        let generation = match *slot {
+            Entry::Free { next_free, generation } => {
+                self.first_free = next_free;
+                generation
+            }
+            Entry::Occupied { .. } => unreachable!("free list pointed at an occupied slot"),
+            Entry::Retired => unreachable!("free list pointed at a retired slot"),
+        };
+        *slot = Entry::Occupied { value, generation };
+        self.allocated += 1;
         // Return a pointer that includes the current generation.
-        TinyPointer { index: idx as u32, generation: slot.generation }
+        TinyPointer { index: I::from_usize(idx), generation }
     }
 
     /// Returns an immutable reference to the value corresponding to `ptr`,
     /// or `None` if the pointer is invalid, the slot is free, or the generation mismatches.
-    pub fn get(&self, ptr: TinyPointer) -> Option<&T> {
-        self.slots.get(ptr.index()).and_then(|slot| {
-            if slot.generation == ptr.generation {
-                slot.value.as_ref().map(|boxed| &**boxed)
-            } else {
-                None
-            }
-        })
+    pub fn get(&self, ptr: TinyPointer<I, G>) -> Option<&T> {
+        if ptr.index() >= self.capacity {
+            return None;
+        }
+        let (page, offset) = self.locate(ptr.index());
+        match &self.pages[page][offset] {
+            Entry::Occupied { value, generation } if *generation == ptr.generation => Some(value),
+            _ => None,
+        }
     }
 
     /// Returns a mutable reference to the value corresponding to `ptr`,
     /// or `None` if the pointer is invalid, the slot is free, or the generation mismatches.
-    pub fn get_mut(&mut self, ptr: TinyPointer) -> Option<&mut T> {
-        self.slots.get_mut(ptr.index()).and_then(|slot| {
-            if slot.generation == ptr.generation {
-                slot.value.as_mut().map(|boxed| &mut **boxed)
-            } else {
-                None
-            }
-        })
+    pub fn get_mut(&mut self, ptr: TinyPointer<I, G>) -> Option<&mut T> {
+        if ptr.index() >= self.capacity {
+            return None;
+        }
+        let (page, offset) = self.locate(ptr.index());
+        match &mut self.pages[page][offset] {
+            Entry::Occupied { value, generation } if *generation == ptr.generation => Some(value),
+            _ => None,
+        }
     }
 
     /// Frees the value at `ptr` and returns it.
     ///
-    /// If the pointer's generation matches, the slot is freed and its generation is incremented.
-    /// The freed slot is then added back to the free list.
-    pub fn free(&mut self, ptr: TinyPointer) -> Option<T> {
+    /// If the pointer's generation matches, the slot is freed, its generation
+    /// is incremented, and it becomes the new head of the free list — unless
+    /// the generation was already at `G::MAX`, in which case incrementing it
+    /// would wrap back to a value a stale pointer could carry. In that case
+    /// the slot is permanently retired instead: it is never returned to the
+    /// free list, so its index is never handed out again. See
+    /// [`retired`](Self::retired).
+    pub fn free(&mut self, ptr: TinyPointer<I, G>) -> Option<T> {
         let idx = ptr.index();
-        if idx < self.slots.len() {
-            let slot = &mut self.slots[idx];
-            // Only free if the generation matches.
-            if slot.generation == ptr.generation {
-                let value = slot.value.take();
-                // Increment generation to invalidate any stale pointers.
-                slot.generation = slot.generation.wrapping_add(1);
-                self.free_list.push(idx);
-                return value.map(|boxed| *boxed);
-            }
+        if idx >= self.capacity {
+            return None;
+        }
+        let (page, offset) = self.locate(idx);
+        let next_free = self.first_free;
+        let slot = &mut self.pages[page][offset];
+        if !matches!(slot, Entry::Occupied { generation, .. } if *generation == ptr.generation) {
+            return None;
+        }
+        // Swap in a placeholder so we can move the value out of the occupied entry.
+        let old = std::mem::replace(slot, Entry::Free { next_free, generation: G::ZERO });
+        let (value, generation) = match old {
+            Entry::Occupied { value, generation } => (value, generation),
+            _ => unreachable!("checked above"),
+        };
+        if generation == G::MAX {
+            *slot = Entry::Retired;
+            self.retired += 1;
+        } else {
+            // Increment generation to invalidate any stale pointers, and rethread
+            // this slot onto the head of the free list.
+            *slot = Entry::Free { next_free, generation: generation.wrapping_add_one() };
+            self.first_free = Some(I::from_usize(idx));
         }
-        None
+        self.allocated -= 1;
+        Some(value)
     }
 
-    /// Resizes the table by doubling its capacity.
+    /// Grows the table by allocating a new page.
     ///
     /// This method is automatically called from `allocate()` when no free slots remain.
+    /// Existing pages are never moved or reallocated, so previously returned
+    /// pointers and references remain valid across the growth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocating the new page fails, or if it would exceed the
+    /// maximum index representable by `I`. Use [`try_reserve`](Self::try_reserve)
+    /// to grow the table without risking an abort.
     pub fn resize(&mut self) {
-        let old_capacity = self.capacity();
-        let new_capacity = old_capacity * 2;
-        self.slots.reserve(new_capacity - old_capacity);
-        for i in old_capacity..new_capacity {
-            self.slots.push(Slot { value: None, generation: 0 });
-            self.free_list.push(i);
+        self.try_reserve().expect("failed to allocate a new page");
+    }
+
+    /// Fallibly grows the table by one page, using `Vec::try_reserve_exact`
+    /// so an allocation failure is reported as an error instead of aborting
+    /// the process. Also reports, rather than panics on, the new page
+    /// pushing the table's capacity past the maximum index representable by
+    /// `I` — see [`ReserveError::IndexSpaceExhausted`].
+    pub fn try_reserve(&mut self) -> Result<(), ReserveError> {
+        let page_index = self.pages.len() as u32;
+        let new_page_len = 1usize << (page_index + self.base);
+        let start_index = self.capacity;
+        if start_index + new_page_len > I::MAX.to_usize() {
+            return Err(ReserveError::IndexSpaceExhausted);
         }
+
+        self.pages.try_reserve(1)?;
+        let page = Self::try_new_free_page(start_index, new_page_len, self.first_free)?;
+        self.pages.push(page);
+        self.first_free = Some(I::from_usize(start_index));
+        self.capacity += new_page_len;
+        Ok(())
+    }
+
+    /// Returns an iterator over all currently occupied entries, yielding
+    /// each live value alongside the `TinyPointer` that still addresses it.
+    pub fn iter(&self) -> impl Iterator<Item = (TinyPointer<I, G>, &T)> + '_ {
+        let base = self.base;
+        self.pages.iter().enumerate().flat_map(move |(page_idx, page)| {
+            let page_start = Self::page_start(page_idx, base);
+            page.iter().enumerate().filter_map(move |(offset, entry)| match entry {
+                Entry::Occupied { value, generation } => Some((
+                    TinyPointer { index: I::from_usize(page_start + offset), generation: *generation },
+                    value,
+                )),
+                Entry::Free { .. } | Entry::Retired => None,
+            })
+        })
+    }
+
+    /// Returns a mutable iterator over all currently occupied entries, yielding
+    /// each live value alongside the `TinyPointer` that still addresses it.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (TinyPointer<I, G>, &mut T)> + '_ {
+        let base = self.base;
+        self.pages.iter_mut().enumerate().flat_map(move |(page_idx, page)| {
+            let page_start = Self::page_start(page_idx, base);
+            page.iter_mut().enumerate().filter_map(move |(offset, entry)| match entry {
+                Entry::Occupied { value, generation } => Some((
+                    TinyPointer { index: I::from_usize(page_start + offset), generation: *generation },
+                    value,
+                )),
+                Entry::Free { .. } | Entry::Retired => None,
+            })
+        })
+    }
+
+    /// Drains every occupied entry out of the table, leaving it empty.
+    ///
+    /// Each yielded item carries the `TinyPointer` that addressed it. As
+    /// entries are yielded their slots are freed and rethreaded onto the
+    /// free list with an incremented generation, exactly as `free` does, so
+    /// any outstanding pointer to a drained entry is invalidated. Dropping
+    /// the `Drain` before it is exhausted finishes draining the remainder.
+    pub fn drain(&mut self) -> Drain<'_, T, I, G> {
+        Drain { table: self, page_idx: 0, offset: 0 }
+    }
+}
+
+/// An iterator that consumes a `DynamicTinyPointerTable`, yielding each
+/// occupied entry as an owned value alongside its `TinyPointer`. Created by
+/// the table's [`IntoIterator`] implementation.
+pub struct IntoIter<T, I, G> {
+    pages: Vec<FreePage<T, I, G>>,
+    base: u32,
+    page_idx: usize,
+    offset: usize,
+}
+
+impl<T, I: PtrInx, G: PtrGen> Iterator for IntoIter<T, I, G> {
+    type Item = (TinyPointer<I, G>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.page_idx >= self.pages.len() {
+                return None;
+            }
+            if self.offset >= self.pages[self.page_idx].len() {
+                self.page_idx += 1;
+                self.offset = 0;
+                continue;
+            }
+            let page_start = DynamicTinyPointerTable::<T, I, G>::page_start(self.page_idx, self.base);
+            let idx = page_start + self.offset;
+            let entry = std::mem::replace(
+                &mut self.pages[self.page_idx][self.offset],
+                Entry::Free { next_free: None, generation: G::ZERO },
+            );
+            self.offset += 1;
+            if let Entry::Occupied { value, generation } = entry {
+                return Some((TinyPointer { index: I::from_usize(idx), generation }, value));
+            }
+        }
+    }
+}
+
+impl<T, I: PtrInx, G: PtrGen> IntoIterator for DynamicTinyPointerTable<T, I, G> {
+    type Item = (TinyPointer<I, G>, T);
+    type IntoIter = IntoIter<T, I, G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { pages: self.pages, base: self.base, page_idx: 0, offset: 0 }
+    }
+}
+
+/// An iterator that drains every occupied entry out of a
+/// `DynamicTinyPointerTable`, freeing each slot as it is yielded. Created by
+/// [`DynamicTinyPointerTable::drain`].
+pub struct Drain<'a, T, I: PtrInx, G: PtrGen> {
+    table: &'a mut DynamicTinyPointerTable<T, I, G>,
+    page_idx: usize,
+    offset: usize,
+}
+
+impl<'a, T, I: PtrInx, G: PtrGen> Iterator for Drain<'a, T, I, G> {
+    type Item = (TinyPointer<I, G>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.page_idx >= self.table.pages.len() {
+                return None;
+            }
+            if self.offset >= self.table.pages[self.page_idx].len() {
+                self.page_idx += 1;
+                self.offset = 0;
+                continue;
+            }
+            let page_start =
+                DynamicTinyPointerTable::<T, I, G>::page_start(self.page_idx, self.table.base);
+            let idx = page_start + self.offset;
+            let next_free = self.table.first_free;
+            let slot = &mut self.table.pages[self.page_idx][self.offset];
+            self.offset += 1;
+            if !matches!(slot, Entry::Occupied { .. }) {
+                continue;
+            }
+            let old = std::mem::replace(slot, Entry::Free { next_free, generation: G::ZERO });
+            let (value, generation) = match old {
+                Entry::Occupied { value, generation } => (value, generation),
+                _ => unreachable!("checked above"),
+            };
+            if generation == G::MAX {
+                *slot = Entry::Retired;
+                self.table.retired += 1;
+            } else {
+                *slot = Entry::Free { next_free, generation: generation.wrapping_add_one() };
+                self.table.first_free = Some(I::from_usize(idx));
+            }
+            self.table.allocated -= 1;
+            return Some((TinyPointer { index: I::from_usize(idx), generation }, value));
+        }
+    }
+}
+
+impl<'a, T, I: PtrInx, G: PtrGen> Drop for Drain<'a, T, I, G> {
+    fn drop(&mut self) {
+        for _ in self.by_ref() {}
     }
 }
 
@@ -234,7 +755,7 @@ mod tests {
         let ptr2 = table.allocate(2);
         assert_eq!(table.capacity(), 2);
         // Free list should now be empty.
-        assert!(table.free_list.is_empty());
+        assert!(table.first_free.is_none());
         // Allocate one more; this triggers resize.
         let ptr3 = table.allocate(3);
         assert!(table.capacity() >= 3);
@@ -291,4 +812,155 @@ mod tests {
         // The new pointer should access the new value.
         assert_eq!(table.get(new_ptr), Some(&200));
     }
+
+    /// Tests that a `TinyPointer` survives a round trip through `to_bits`/`from_bits`,
+    /// and that the reserved null sentinel decodes to `None`.
+    #[test]
+    fn test_bits_round_trip() {
+        let mut table = DynamicTinyPointerTable::new(4);
+        let ptr = table.allocate(7);
+        let bits = ptr.to_bits();
+        assert_eq!(TinyPointer::from_bits(bits), Some(ptr));
+        assert_eq!(TinyPointer::<u32, u32>::from_bits(u64::from(u32::MAX)), None);
+    }
+
+    /// Tests that references into earlier pages remain stable across growth:
+    /// a pointer allocated before several resizes still resolves correctly.
+    #[test]
+    fn test_paged_growth_stability() {
+        let mut table = DynamicTinyPointerTable::new(2);
+        let first = table.allocate(1);
+        for i in 2..200 {
+            table.allocate(i);
+        }
+        assert_eq!(table.get(first), Some(&1));
+        assert!(table.capacity() >= 200);
+    }
+
+    /// Tests that `iter`/`iter_mut` only visit occupied slots, and that
+    /// mutations made through `iter_mut` are observed by `get`.
+    #[test]
+    fn test_iter_and_iter_mut() {
+        let mut table = DynamicTinyPointerTable::new(4);
+        let ptr_a = table.allocate(1);
+        let ptr_b = table.allocate(2);
+        let ptr_c = table.allocate(3);
+        table.free(ptr_b);
+
+        let mut seen: Vec<i32> = table.iter().map(|(_, v)| *v).collect();
+        seen.sort();
+        assert_eq!(seen, vec![1, 3]);
+
+        for (_, value) in table.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!(table.get(ptr_a), Some(&10));
+        assert_eq!(table.get(ptr_c), Some(&30));
+    }
+
+    /// Tests that the owning `IntoIterator` implementation yields every
+    /// occupied entry exactly once.
+    #[test]
+    fn test_into_iter() {
+        let mut table = DynamicTinyPointerTable::new(4);
+        let ptr = table.allocate(1);
+        table.allocate(2);
+        table.free(ptr);
+        table.allocate(3);
+
+        let mut values: Vec<i32> = table.into_iter().map(|(_, v)| v).collect();
+        values.sort();
+        assert_eq!(values, vec![2, 3]);
+    }
+
+    /// Tests that `drain` yields every occupied entry and empties the table,
+    /// and that the freed slots are available for reuse afterwards.
+    #[test]
+    fn test_drain() {
+        let mut table = DynamicTinyPointerTable::new(4);
+        table.allocate(1);
+        table.allocate(2);
+        table.allocate(3);
+
+        let mut drained: Vec<i32> = table.drain().map(|(_, v)| v).collect();
+        drained.sort();
+        assert_eq!(drained, vec![1, 2, 3]);
+        assert_eq!(table.allocated(), 0);
+
+        let ptr = table.allocate(42);
+        assert_eq!(table.get(ptr), Some(&42));
+    }
+
+    /// Tests that `try_allocate` behaves like `allocate` on the happy path,
+    /// including triggering a fallible reservation when the table is full.
+    #[test]
+    fn test_try_allocate() {
+        let mut table = DynamicTinyPointerTable::new(2);
+        let ptr1 = table.try_allocate(1).expect("should allocate");
+        let ptr2 = table.try_allocate(2).expect("should allocate");
+        // Free list is now exhausted, so this must trigger try_reserve.
+        let ptr3 = table.try_allocate(3).expect("should grow and allocate");
+        assert_eq!(table.get(ptr1), Some(&1));
+        assert_eq!(table.get(ptr2), Some(&2));
+        assert_eq!(table.get(ptr3), Some(&3));
+    }
+
+    /// Tests that `try_allocate` reports index-space exhaustion as an
+    /// `Err` instead of panicking, returning the value that could not be
+    /// stored.
+    #[test]
+    fn test_try_allocate_index_space_exhausted() {
+        let mut table = DynamicTinyPointerTable::<i32, u8, u8>::with_widths(128);
+        for _ in 0..table.capacity() {
+            table.try_allocate(0).expect("should fit within the first page");
+        }
+
+        match table.try_allocate(42) {
+            Err(AllocError { value, source: ReserveError::IndexSpaceExhausted }) => {
+                assert_eq!(value, 42);
+            }
+            other => panic!("expected IndexSpaceExhausted, got {other:?}"),
+        }
+    }
+
+    /// Tests that narrower index/generation widths can be selected via
+    /// `with_widths`, producing a table that still behaves correctly.
+    #[test]
+    fn test_narrow_widths() {
+        let mut table = DynamicTinyPointerTable::<i32, u8, u8>::with_widths(4);
+        let ptr_a = table.allocate(1);
+        let ptr_b = table.allocate(2);
+        assert_eq!(table.get(ptr_a), Some(&1));
+        assert_eq!(table.get(ptr_b), Some(&2));
+
+        table.free(ptr_a);
+        assert_eq!(table.get(ptr_a), None);
+
+        let bits = ptr_b.to_bits();
+        assert_eq!(TinyPointer::<u8, u8>::from_bits(bits), Some(ptr_b));
+    }
+
+    /// Tests that a slot is permanently retired instead of recycled once its
+    /// generation counter is exhausted, so its index is never handed out
+    /// again.
+    #[test]
+    fn test_generation_exhaustion_retires_slot() {
+        let mut table = DynamicTinyPointerTable::<i32, u32, u8>::with_widths(1);
+
+        // Cycle the single slot through every generation u8 can represent.
+        let first_ptr = table.allocate(0);
+        let retired_index = first_ptr.index();
+        table.free(first_ptr);
+        for i in 1..=u8::MAX as i32 {
+            let ptr = table.allocate(i);
+            assert_eq!(ptr.index(), retired_index);
+            table.free(ptr);
+        }
+        assert_eq!(table.retired(), 1);
+
+        // The retired slot's index must never be reused.
+        let ptr = table.allocate(999);
+        assert_ne!(ptr.index(), retired_index);
+        assert_eq!(table.get(ptr), Some(&999));
+    }
 }